@@ -0,0 +1,276 @@
+use crate::errors::{GitTerminalError, RepositoryError};
+use failure::Error;
+use std::path::Path;
+
+/// Abstracts the handful of repository-mutating operations `Committer` needs, so the
+/// tool isn't hardwired to any one VCS implementation. Modelled after jujutsu-lib's
+/// backend trait, but trimmed to exactly what this tool writes: a tree, a stream of
+/// commit objects, and a HEAD reset.
+pub trait Backend {
+    /// Opens the repository at `repo_path`.
+    fn open(repo_path: &Path) -> Result<Self, Error>
+    where
+        Self: Sized;
+
+    /// Returns the id HEAD currently points at, or `None` for an unborn branch.
+    fn head(&self) -> Result<Option<String>, Error>;
+
+    /// Writes the current index as a tree and returns its id.
+    fn write_tree(&mut self) -> Result<String, Error>;
+
+    /// Writes an already-serialized commit object and returns its id.
+    fn write_commit_object(&self, bytes: Vec<u8>) -> Result<String, Error>;
+
+    /// Returns the configured `"Name <email>"` signature commits are authored as.
+    fn author_signature(&self) -> Result<String, Error>;
+
+    /// Resets HEAD to `oid`. Index handling is backend-defined: `Git2Backend` performs
+    /// a mixed reset (the index is updated to match `oid`'s tree); other backends may
+    /// only move the ref. Check the implementing backend before relying on index state.
+    fn reset_head(&self, oid: &str) -> Result<(), Error>;
+
+    /// Best-effort removal of a generated object, used by `Committer::undo`. Objects
+    /// that are already packed or missing are silently skipped.
+    fn prune(&self, oid: &str);
+
+    /// Returns the committer `(unix_seconds, utc_offset_seconds)` for every commit whose
+    /// committer email is `author_email` reachable from HEAD, in this repository's full
+    /// history. The offset is the commit's own recorded timezone offset, not the host's.
+    fn commit_history(&self, author_email: &str) -> Result<Vec<(i64, i32)>, Error>;
+}
+
+/// The default backend, built on `git2` (libgit2).
+pub struct Git2Backend {
+    repo: git2::Repository,
+}
+
+impl Backend for Git2Backend {
+    fn open(repo_path: &Path) -> Result<Git2Backend, Error> {
+        let repo = git2::Repository::open(repo_path).map_err(|_| RepositoryError::OpenError {})?;
+        Ok(Git2Backend { repo })
+    }
+
+    fn head(&self) -> Result<Option<String>, Error> {
+        Ok(self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.target())
+            .map(|oid| oid.to_string()))
+    }
+
+    fn write_tree(&mut self) -> Result<String, Error> {
+        let mut index = self
+            .repo
+            .index()
+            .map_err(|_| RepositoryError::FindIndexError {})?;
+
+        let tree = index
+            .write_tree()
+            .map_err(|_| RepositoryError::TreeWriteError {})?;
+
+        Ok(tree.to_string())
+    }
+
+    fn write_commit_object(&self, bytes: Vec<u8>) -> Result<String, Error> {
+        let oid = self
+            .repo
+            .odb()
+            .map_err(|_| GitTerminalError::CommitObjectError {})?
+            .write(git2::ObjectType::Commit, &bytes)
+            .map_err(|_| GitTerminalError::CommitObjectError {})?;
+
+        Ok(oid.to_string())
+    }
+
+    fn author_signature(&self) -> Result<String, Error> {
+        let signature = self
+            .repo
+            .signature()
+            .map_err(|_| RepositoryError::SignatureRetrievalError {})?;
+
+        let name = signature
+            .name()
+            .ok_or(RepositoryError::NameRetrievalError {})?;
+        let email = signature
+            .email()
+            .ok_or(RepositoryError::EmailRetrievalError {})?;
+
+        Ok(format!("{} <{}>", name, email))
+    }
+
+    fn reset_head(&self, oid: &str) -> Result<(), Error> {
+        let oid = git2::Oid::from_str(oid).map_err(|_| GitTerminalError::ResetHeadError {})?;
+        let obj = self
+            .repo
+            .find_object(oid, Some(git2::ObjectType::Commit))
+            .map_err(|_| GitTerminalError::ResetHeadError {})?;
+
+        self.repo
+            .reset(&obj, git2::ResetType::Mixed, None)
+            .map_err(|_| GitTerminalError::ResetHeadError {})?;
+
+        Ok(())
+    }
+
+    fn prune(&self, oid: &str) {
+        let path = self
+            .repo
+            .path()
+            .join("objects")
+            .join(&oid[0..2])
+            .join(&oid[2..]);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    fn commit_history(&self, author_email: &str) -> Result<Vec<(i64, i32)>, Error> {
+        let mut revwalk = self.repo.revwalk().map_err(|_| RepositoryError::OpenError {})?;
+        revwalk
+            .push_head()
+            .map_err(|_| RepositoryError::OpenError {})?;
+        revwalk
+            .set_sorting(git2::Sort::TIME)
+            .map_err(|_| RepositoryError::OpenError {})?;
+
+        let mut history = Vec::new();
+        for oid in revwalk {
+            let oid = match oid {
+                Ok(oid) => oid,
+                Err(_) => continue,
+            };
+            let commit = match self.repo.find_commit(oid) {
+                Ok(commit) => commit,
+                Err(_) => continue,
+            };
+            if commit.committer().email() != Some(author_email) {
+                continue;
+            }
+            let when = commit.committer().when();
+            history.push((when.seconds(), when.offset_minutes() * 60));
+        }
+
+        Ok(history)
+    }
+}
+
+/// A pure-Rust backend built on `gix` (gitoxide): its operations never call into
+/// libgit2. `Git2Backend` is still compiled into the same binary, so libgit2 stays a
+/// build dependency.
+pub struct GixBackend {
+    repo: gix::Repository,
+}
+
+impl Backend for GixBackend {
+    fn open(repo_path: &Path) -> Result<GixBackend, Error> {
+        let repo = gix::open(repo_path).map_err(|_| RepositoryError::OpenError {})?;
+        Ok(GixBackend { repo })
+    }
+
+    fn head(&self) -> Result<Option<String>, Error> {
+        Ok(self.repo.head_id().ok().map(|id| id.to_string()))
+    }
+
+    fn write_tree(&mut self) -> Result<String, Error> {
+        let mut index = self
+            .repo
+            .open_index()
+            .map_err(|_| RepositoryError::FindIndexError {})?;
+
+        let tree_id = index
+            .write_tree(&self.repo.objects)
+            .map_err(|_| RepositoryError::TreeWriteError {})?;
+
+        Ok(tree_id.to_string())
+    }
+
+    fn write_commit_object(&self, bytes: Vec<u8>) -> Result<String, Error> {
+        let id = self
+            .repo
+            .objects
+            .write_buf(gix::objs::Kind::Commit, &bytes)
+            .map_err(|_| GitTerminalError::CommitObjectError {})?;
+
+        Ok(id.to_string())
+    }
+
+    fn author_signature(&self) -> Result<String, Error> {
+        let config = self.repo.config_snapshot();
+        let name = config
+            .string("user.name")
+            .ok_or(RepositoryError::NameRetrievalError {})?;
+        let email = config
+            .string("user.email")
+            .ok_or(RepositoryError::EmailRetrievalError {})?;
+
+        Ok(format!("{} <{}>", name, email))
+    }
+
+    // Ref-only: unlike Git2Backend's mixed reset, this does not update the index.
+    fn reset_head(&self, oid: &str) -> Result<(), Error> {
+        let id =
+            gix::ObjectId::from_hex(oid.as_bytes()).map_err(|_| GitTerminalError::ResetHeadError {})?;
+
+        self.repo
+            .edit_reference(gix::refs::transaction::RefEdit {
+                change: gix::refs::transaction::Change::Update {
+                    log: Default::default(),
+                    expected: gix::refs::transaction::PreviousValue::Any,
+                    new: gix::refs::Target::Peeled(id),
+                },
+                name: "HEAD".try_into().map_err(|_| GitTerminalError::ResetHeadError {})?,
+                deref: true,
+            })
+            .map_err(|_| GitTerminalError::ResetHeadError {})?;
+
+        Ok(())
+    }
+
+    fn prune(&self, oid: &str) {
+        let path = self
+            .repo
+            .path()
+            .join("objects")
+            .join(&oid[0..2])
+            .join(&oid[2..]);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    // Walked entirely through gix so that learning from a reference repo under
+    // `--backend gix` never calls into libgit2.
+    fn commit_history(&self, author_email: &str) -> Result<Vec<(i64, i32)>, Error> {
+        let head_id = match self.repo.head_id() {
+            Ok(id) => id,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let walk = self
+            .repo
+            .rev_walk(std::iter::once(head_id))
+            .all()
+            .map_err(|_| RepositoryError::OpenError {})?;
+
+        let mut history = Vec::new();
+        for info in walk {
+            let info = match info {
+                Ok(info) => info,
+                Err(_) => continue,
+            };
+            let commit = match info.id().object().and_then(|object| object.try_into_commit()) {
+                Ok(commit) => commit,
+                Err(_) => continue,
+            };
+            let committer = match commit.committer() {
+                Ok(committer) => committer,
+                Err(_) => continue,
+            };
+            if committer.email != author_email.as_bytes() {
+                continue;
+            }
+            history.push((committer.time.seconds, committer.time.offset));
+        }
+
+        Ok(history)
+    }
+}
@@ -1,15 +1,24 @@
+use crate::backend::{Backend, Git2Backend, GixBackend};
 use crate::dates;
-use crate::errors::{GitTerminalError, RepositoryError};
-use crate::options::Options;
+use crate::errors::{ManifestError, RepositoryError};
+use crate::manifest::{self, Manifest};
+use crate::options::{BackendKind, Options};
 use crate::writer;
 use failure::Error;
-use git2::ObjectType::Commit;
-use git2::ResetType::Mixed;
-use git2::{Oid, Repository};
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
-use std::path::PathBuf;
-use chrono::{DateTime, Local, Duration, NaiveTime};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local, Duration, NaiveDateTime, NaiveTime, Timelike};
+
+/// An empirical commit distribution learned from a reference repository's history,
+/// used in place of the hardcoded weights to make generated history look realistic.
+struct LearnedDistribution {
+    commits_per_day_choices: Vec<i32>,
+    commits_per_day_weights: WeightedIndex<u32>,
+    hour_of_day_weights: WeightedIndex<u32>,
+}
 
 /// A Committer does the work of issuing git commits.
 pub struct Committer {
@@ -20,21 +29,55 @@ pub struct Committer {
     start_datetime: DateTime<Local>,
     start_hour: NaiveTime,
     end_hour: NaiveTime,
-    repo: Repository,
+    backend: Box<dyn Backend>,
+    learned: Option<LearnedDistribution>,
+    generated_commit_times: RefCell<Vec<i64>>,
+    merge_probability: f64,
+    branch_length: i32,
+    manifest_path: Option<PathBuf>,
+    original_head: RefCell<Option<String>>,
+    generated_oids: RefCell<Vec<String>>,
 }
 
+/// Implied working-time stats for a run of generated commits, as estimated by the
+/// git-hours session algorithm.
+#[derive(Debug)]
+pub struct HoursEstimate {
+    pub total_hours: f64,
+    pub avg_hours_per_day: f64,
+    pub commits_per_hour: f64,
+}
+
+/// Default gap, in hours, at or above which two consecutive commits are considered separate
+/// sessions, and default amount of time credited to the first commit of each session.
+const DEFAULT_SESSION_GAP_HOURS: i64 = 2;
+const DEFAULT_FIRST_COMMIT_ADDITION_HOURS: i64 = 2;
+
 impl Committer {
     /// Creates a new Committer.
     pub fn new(options: Options) -> Result<Committer, Error> {
-        let mut repo = Committer::get_repository(&options.repo)?;
-        let tree = Committer::create_tree(&mut repo)?;
-        let author = Committer::get_author(&repo)?;
+        let mut backend: Box<dyn Backend> = match options.backend {
+            BackendKind::Git2 => Box::new(Git2Backend::open(&options.repo)?),
+            BackendKind::Gix => Box::new(GixBackend::open(&options.repo)?),
+        };
+        let tree = backend.write_tree()?;
+        let author = backend.author_signature()?;
 
         let days_to_commit = (365.0 * options.yrs_ago).round() as i64;
         let now = Local::now();
         let corrected_now = now - (now.time() - options.start);
         let start_datetime = corrected_now - Duration::days(days_to_commit);
 
+        let learned = match &options.reference_repo {
+            Some(reference_repo) => Committer::learn_from_reference(
+                reference_repo,
+                &author,
+                Duration::minutes(options.max_commit_diff),
+                options.backend,
+            )?,
+            None => None,
+        };
+
         return Ok(Committer {
             tree,
             author,
@@ -43,131 +86,539 @@ impl Committer {
             start_datetime,
             start_hour: options.start,
             end_hour: options.end,
-            repo,
+            backend,
+            learned,
+            generated_commit_times: RefCell::new(Vec::new()),
+            merge_probability: options.merge_probability,
+            branch_length: options.branch_length,
+            manifest_path: options.manifest_path,
+            original_head: RefCell::new(None),
+            generated_oids: RefCell::new(Vec::new()),
         });
     }
 
+    /// Learns an empirical commit-time distribution from `reference_repo`, mirroring how
+    /// git-hours estimates activity: commits by `author` are grouped into coding sessions
+    /// (a gap below `max_commit_diff` keeps a session alive), and the resulting sessions
+    /// are turned into a commits-per-active-day histogram and an hour-of-day histogram.
+    /// Returns `None` if the reference repository has no commits from `author`, in which
+    /// case callers should fall back to the hardcoded weights.
+    fn learn_from_reference(
+        reference_repo: &PathBuf,
+        author: &str,
+        max_commit_diff: Duration,
+        backend_kind: BackendKind,
+    ) -> Result<Option<LearnedDistribution>, Error> {
+        let author_email = Committer::extract_email(author);
+
+        // Opened through the same backend kind as the target repository, so picking
+        // `--backend gix` doesn't pull in libgit2 just to read `--reference-repo`.
+        let reference_backend: Box<dyn Backend> = match backend_kind {
+            BackendKind::Git2 => Box::new(Git2Backend::open(reference_repo)?),
+            BackendKind::Gix => Box::new(GixBackend::open(reference_repo)?),
+        };
+
+        let history = reference_backend.commit_history(author_email)?;
+        Committer::build_distribution(history, max_commit_diff)
+    }
+
+    /// Turns a set of `(unix_seconds, utc_offset_seconds)` commit timestamps (any order)
+    /// into the two learned histograms, or `None` if there are no timestamps to learn
+    /// from. Commits are first grouped into sessions (see `group_into_sessions`); a whole
+    /// session's commits are attributed to the session's first day, so a late-night
+    /// session that crosses midnight counts as one active day rather than splitting
+    /// across two.
+    fn build_distribution(
+        mut history: Vec<(i64, i32)>,
+        max_commit_diff: Duration,
+    ) -> Result<Option<LearnedDistribution>, Error> {
+        if history.is_empty() {
+            return Ok(None);
+        }
+
+        history.sort_by_key(|(ts, _)| *ts);
+        let timestamps: Vec<i64> = history.iter().map(|(ts, _)| *ts).collect();
+        let sessions = Committer::group_into_sessions(&timestamps, max_commit_diff.num_seconds());
+
+        let mut per_day_counts: HashMap<i64, u32> = HashMap::new();
+        for session in &sessions {
+            let session_day = session.first().unwrap().div_euclid(86_400);
+            *per_day_counts.entry(session_day).or_insert(0) += session.len() as u32;
+        }
+
+        let first_day = timestamps.first().unwrap().div_euclid(86_400);
+        let last_day = timestamps.last().unwrap().div_euclid(86_400);
+        let total_days = (last_day - first_day + 1).max(1) as u32;
+        let idle_days = total_days.saturating_sub(per_day_counts.len() as u32);
+
+        let mut day_frequency: HashMap<i32, u32> = HashMap::new();
+        if idle_days > 0 {
+            *day_frequency.entry(0).or_insert(0) += idle_days;
+        }
+        for count in per_day_counts.values() {
+            *day_frequency.entry(*count as i32).or_insert(0) += 1;
+        }
+
+        let mut commits_per_day_choices: Vec<i32> = day_frequency.keys().cloned().collect();
+        commits_per_day_choices.sort();
+        let commits_per_day_weights: Vec<u32> = commits_per_day_choices
+            .iter()
+            .map(|choice| day_frequency[choice])
+            .collect();
+
+        // Bucketed using each commit's own recorded UTC offset, not the host machine's
+        // `Local` zone, so a reference repo authored in a different timezone than this
+        // run doesn't shift the learned hour-of-day distribution.
+        let mut hour_weights = [0u32; 24];
+        for &(ts, offset_seconds) in &history {
+            let local_ts = ts + i64::from(offset_seconds);
+            let hour = NaiveDateTime::from_timestamp_opt(local_ts, 0)
+                .map(|dt| dt.hour())
+                .unwrap_or(0) as usize;
+            hour_weights[hour] += 1;
+        }
+
+        let commits_per_day_weights = WeightedIndex::new(&commits_per_day_weights)
+            .map_err(|_| RepositoryError::OpenError {})?;
+        let hour_of_day_weights =
+            WeightedIndex::new(&hour_weights).map_err(|_| RepositoryError::OpenError {})?;
+
+        Ok(Some(LearnedDistribution {
+            commits_per_day_choices,
+            commits_per_day_weights,
+            hour_of_day_weights,
+        }))
+    }
+
+    /// Groups sorted `timestamps` (unix seconds) into coding sessions: a new session
+    /// starts whenever the gap to the previous timestamp is at least `max_gap_seconds`.
+    /// A lone commit with no neighbour within the gap forms a one-commit session.
+    fn group_into_sessions(timestamps: &[i64], max_gap_seconds: i64) -> Vec<Vec<i64>> {
+        let mut sessions: Vec<Vec<i64>> = Vec::new();
+        for &ts in timestamps {
+            match sessions.last_mut() {
+                Some(session) if ts - session.last().unwrap() < max_gap_seconds => {
+                    session.push(ts);
+                }
+                _ => sessions.push(vec![ts]),
+            }
+        }
+        sessions
+    }
+
+    /// Pulls the `<email>` portion out of a `"Name <email>"` signature string. Falls
+    /// back to the whole string if it has no brackets or a `>` comes before the first
+    /// `<`. With only one bracket present, the text on its far side is returned.
+    fn extract_email(author: &str) -> &str {
+        let start = author.find('<').map(|i| i + 1).unwrap_or(0);
+        let end = author.find('>').unwrap_or_else(|| author.len());
+        author.get(start..end).unwrap_or(author)
+    }
+
     /// This method can be called to write all commits from yrs ago to current date.
     pub fn commit_all(&self) -> Result<(), Error> {
+        *self.original_head.borrow_mut() = self.backend.head()?;
+
         // Write init commit.
         let mut commit_time = self.start_datetime;
         let mut blob =
             writer::generate_initial_blob(&self.tree, &self.author, &self.message, commit_time)?;
         let mut parent = self.commit_blob(blob.clone().into_bytes())?;
+        self.record_commit_time(commit_time);
         let work_duration = self.end_hour - self.start_hour;
 
         // Main loop to write commits up until present day.
+        let mut rng = rand::thread_rng();
         for _ in 1..self.days_to_commit {
             commit_time = commit_time + Duration::days(1);
-            let (p, b) = self.commit_from_time(&parent, &blob, commit_time, work_duration)?;
+
+            if dates::should_skip_date(commit_time.date()) {
+                continue;
+            }
+
+            let (p, b) = if rng.gen::<f64>() < self.merge_probability {
+                self.commit_branch_and_merge(&parent, &blob, commit_time)?
+            } else {
+                self.commit_from_time(&parent, &blob, commit_time, work_duration)?
+            };
             parent = p;
             blob = b;
         }
 
         // Reset head at end.
-        self.reset_head_to_hash(parent)?;
+        self.reset_head_to_hash(&parent)?;
+
+        if let Some(manifest_path) = &self.manifest_path {
+            self.write_manifest(manifest_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serializes everything generated by this run into an undo manifest at `path`.
+    fn write_manifest(&self, path: &Path) -> Result<(), Error> {
+        let times = self.generated_commit_times.borrow();
+        let original_head = self.original_head.borrow().clone().unwrap_or_default();
+
+        let manifest = Manifest {
+            version: manifest::MANIFEST_VERSION,
+            original_head,
+            author: self.author.clone(),
+            range_start: times.iter().cloned().min().unwrap_or(0),
+            range_end: times.iter().cloned().max().unwrap_or(0),
+            generated_oids: self.generated_oids.borrow().clone(),
+        };
+
+        manifest.write_to(path)
+    }
+
+    /// Reverses a run recorded in the undo manifest at `manifest_path`: resets HEAD back
+    /// to the Oid captured before the run started and prunes the generated commit
+    /// objects from the object database.
+    pub fn undo(&self, manifest_path: &Path) -> Result<(), Error> {
+        let mapped = manifest::MappedManifest::open(manifest_path)?;
+        let archived = mapped.archived()?;
+
+        if archived.version != manifest::MANIFEST_VERSION {
+            return Err(ManifestError::VersionMismatchError {}.into());
+        }
+
+        self.reset_head_to_hash(archived.original_head.as_str())?;
+
+        for oid in archived.generated_oids.iter() {
+            self.backend.prune(oid.as_str());
+        }
+
+        Ok(())
+    }
+
+    /// Builds the same per-day commit schedule as `commit_all`, but instead of writing
+    /// any objects, prints the plan: each day's absolute date, a human-friendly relative
+    /// description, the sampled commit count, and any day skipped as a weekend.
+    pub fn dry_run(&self) -> Result<(), Error> {
+        let mut commit_time = self.start_datetime;
+        println!(
+            "{} ({}) - 1 commit (initial commit)",
+            commit_time.date(),
+            self.humanize_relative(commit_time)
+        );
+
+        let work_duration = self.end_hour - self.start_hour;
+        let mut rng = rand::thread_rng();
+
+        for _ in 1..self.days_to_commit {
+            commit_time = commit_time + Duration::days(1);
+            let relative = self.humanize_relative(commit_time);
+
+            if dates::should_skip_date(commit_time.date()) {
+                println!("{} ({}) - skipped (weekend)", commit_time.date(), relative);
+                continue;
+            }
+
+            if rng.gen::<f64>() < self.merge_probability {
+                println!(
+                    "{} ({}) - topic branch + merge ({} commits)",
+                    commit_time.date(),
+                    relative,
+                    self.branch_length + 1
+                );
+                continue;
+            }
+
+            let num_of_commits = self.gen_rand_num_commits();
+            let mut planned = 0;
+            for i in 0..num_of_commits {
+                let candidate = self.pick_commit_time(commit_time, work_duration, i, num_of_commits);
+                if !dates::should_skip_date(candidate.date()) {
+                    planned += 1;
+                }
+            }
+
+            println!("{} ({}) - {} commit(s)", commit_time.date(), relative, planned);
+        }
 
         Ok(())
     }
 
+    /// Renders the offset between `commit_time` and now as a short relative
+    /// description, e.g. "3 months ago" or "2 years ago".
+    fn humanize_relative(&self, commit_time: DateTime<Local>) -> String {
+        let days = (Local::now() - commit_time).num_days();
+
+        let (amount, unit) = if days >= 365 {
+            (days / 365, "year")
+        } else if days >= 30 {
+            (days / 30, "month")
+        } else if days >= 7 {
+            (days / 7, "week")
+        } else if days >= 1 {
+            (days, "day")
+        } else {
+            return "today".to_string();
+        };
+
+        format!("{} {}{} ago", amount, unit, if amount == 1 { "" } else { "s" })
+    }
+
     fn commit_from_time(
         &self,
-        parent: &Oid,
+        parent: &str,
         blob: &String,
         start_time: DateTime<Local>,
         work_duration: Duration,
-    ) -> Result<(Oid, String), Error> {
-        let num_of_commits = Committer::gen_rand_num_commits();
-        let mut parent = parent.clone();
+    ) -> Result<(String, String), Error> {
+        let num_of_commits = self.gen_rand_num_commits();
+        let mut parent = parent.to_string();
         let mut blob = blob.clone();
 
-
         for i in 0..num_of_commits {
-            let commit_time = start_time
-                + Duration::seconds(((work_duration.num_seconds() as f64 / num_of_commits as f64) * (i as f64)) as i64);
+            let commit_time = self.pick_commit_time(start_time, work_duration, i, num_of_commits);
 
             if dates::should_skip_date(commit_time.date()) {
                 continue;
             }
             blob = writer::generate_non_initial_blob(
                 &self.tree,
-                &parent.to_string(),
+                &parent,
                 &self.author,
                 &self.message,
                 commit_time,
             )?;
             parent = self.commit_blob(blob.clone().into_bytes())?;
+            self.record_commit_time(commit_time);
         }
 
         Ok((parent, blob))
     }
 
-    pub fn gen_rand_num_commits() -> i32 {
-        // Generate random number of times to commit today.
+    /// Forks a short-lived topic branch off `parent`, writes `branch_length` commits
+    /// onto it (20 minutes apart, starting at `commit_time`), then merges it back into the
+    /// main line with a real two-parent merge commit dated 10 minutes after the last topic
+    /// commit. Returns the merge commit as the new parent.
+    fn commit_branch_and_merge(
+        &self,
+        parent: &str,
+        blob: &String,
+        commit_time: DateTime<Local>,
+    ) -> Result<(String, String), Error> {
+        let mut branch_tip = parent.to_string();
+        let mut branch_blob = blob.clone();
+
+        for i in 0..self.branch_length {
+            let topic_commit_time = commit_time + Duration::minutes(i64::from(i) * 20);
+            branch_blob = writer::generate_non_initial_blob(
+                &self.tree,
+                &branch_tip,
+                &self.author,
+                &self.message,
+                topic_commit_time,
+            )?;
+            branch_tip = self.commit_blob(branch_blob.clone().into_bytes())?;
+            self.record_commit_time(topic_commit_time);
+        }
+
+        let merge_time = commit_time + Duration::minutes(i64::from(self.branch_length) * 20 + 10);
+        let merge_blob = writer::generate_merge_blob(
+            &self.tree,
+            &[parent.to_string(), branch_tip.clone()],
+            &self.author,
+            &self.message,
+            merge_time,
+        )?;
+        let merge_oid = self.commit_blob(merge_blob.clone().into_bytes())?;
+        self.record_commit_time(merge_time);
+
+        Ok((merge_oid, merge_blob))
+    }
+
+    /// Records a generated commit's timestamp for later use by `estimate_hours`.
+    fn record_commit_time(&self, commit_time: DateTime<Local>) {
+        self.generated_commit_times
+            .borrow_mut()
+            .push(commit_time.timestamp());
+    }
+
+    /// Estimates total working hours implied by the commits generated so far, using the
+    /// git-hours session algorithm, and prints a summary.
+    pub fn estimate_hours(&self) -> HoursEstimate {
+        let timestamps = self.generated_commit_times.borrow().clone();
+        let estimate = Committer::compute_hours_estimate(timestamps.clone());
+
+        println!(
+            "estimated {:.1} working hours across {} commits ({:.1} hours/day, {:.2} commits/hour)",
+            estimate.total_hours,
+            timestamps.len(),
+            estimate.avg_hours_per_day,
+            estimate.commits_per_hour,
+        );
+
+        estimate
+    }
+
+    /// Pure git-hours session algorithm: consecutive commits less than
+    /// `DEFAULT_SESSION_GAP_HOURS` apart count their actual gap toward total hours;
+    /// a larger gap (a new session) instead credits `DEFAULT_FIRST_COMMIT_ADDITION_HOURS`,
+    /// as does the very first commit.
+    fn compute_hours_estimate(mut timestamps: Vec<i64>) -> HoursEstimate {
+        timestamps.sort();
+
+        let session_gap = Duration::hours(DEFAULT_SESSION_GAP_HOURS).num_seconds();
+        let first_commit_addition = Duration::hours(DEFAULT_FIRST_COMMIT_ADDITION_HOURS).num_seconds();
+
+        let mut total_seconds = if timestamps.is_empty() { 0 } else { first_commit_addition };
+        for pair in timestamps.windows(2) {
+            let gap = pair[1] - pair[0];
+            if gap < session_gap {
+                total_seconds += gap;
+            } else {
+                total_seconds += first_commit_addition;
+            }
+        }
+
+        let total_hours = total_seconds as f64 / 3600.0;
+        let distinct_days: std::collections::HashSet<i64> = timestamps
+            .iter()
+            .map(|ts| ts.div_euclid(86_400))
+            .collect();
+        let avg_hours_per_day = if distinct_days.is_empty() {
+            0.0
+        } else {
+            total_hours / distinct_days.len() as f64
+        };
+        let commits_per_hour = if total_hours > 0.0 {
+            timestamps.len() as f64 / total_hours
+        } else {
+            0.0
+        };
+
+        HoursEstimate {
+            total_hours,
+            avg_hours_per_day,
+            commits_per_hour,
+        }
+    }
+
+    pub fn gen_rand_num_commits(&self) -> i32 {
+        let mut rng = rand::thread_rng();
+
+        if let Some(learned) = &self.learned {
+            return learned.commits_per_day_choices[learned.commits_per_day_weights.sample(&mut rng)];
+        }
+
+        // No learned distribution available: fall back to a hardcoded weighted spread.
         // Weight upper and lower numbers more to create believable spread.
         let choices = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
         let weights = [3, 4, 2, 2, 2, 1, 1, 1, 1, 2, 2, 2, 4, 3];
         let dist = WeightedIndex::new(&weights).unwrap();
-        let mut rng = rand::thread_rng();
         choices[dist.sample(&mut rng)]
     }
 
-    fn reset_head_to_hash(&self, hash: Oid) -> Result<(), Error> {
-        let obj = self
-            .repo
-            .find_object(hash, Some(Commit))
-            .map_err(|_| GitTerminalError::ResetHeadError {})?;
+    /// Picks the timestamp for the `i`-th of `num_of_commits` commits on a given day. When a
+    /// learned hour-of-day distribution is available, the hour is sampled from it (clamped to
+    /// `[start_hour, end_hour]`); otherwise the commits are spread linearly across `work_duration`.
+    fn pick_commit_time(
+        &self,
+        start_time: DateTime<Local>,
+        work_duration: Duration,
+        i: i32,
+        num_of_commits: i32,
+    ) -> DateTime<Local> {
+        if let Some(learned) = &self.learned {
+            let mut rng = rand::thread_rng();
+            let hour = learned.hour_of_day_weights.sample(&mut rng) as i64;
+            let clamped_hour = hour
+                .max(i64::from(self.start_hour.hour()))
+                .min(i64::from(self.end_hour.hour()));
+            let offset = Duration::hours(clamped_hour) - Duration::hours(i64::from(self.start_hour.hour()))
+                + Duration::seconds(rng.gen_range(0..3600));
+            // `and_time` returns `None` for a local civil time that doesn't exist (a
+            // DST "spring forward" gap); fall back to `start_time` itself, which is
+            // already a valid instant, rather than panicking mid-run.
+            let base = start_time.date().and_time(self.start_hour).unwrap_or(start_time);
+            return base + offset;
+        }
 
-        self.repo
-            .reset(&obj, Mixed, None)
-            .map_err(|_| GitTerminalError::ResetHeadError {})?;
+        start_time
+            + Duration::seconds(
+                ((work_duration.num_seconds() as f64 / num_of_commits as f64) * (i as f64)) as i64,
+            )
+    }
 
-        Ok(())
+    fn reset_head_to_hash(&self, hash: &str) -> Result<(), Error> {
+        self.backend.reset_head(hash)
     }
 
     // Commits a blob returning the object id.
-    fn commit_blob(&self, blob: Vec<u8>) -> Result<Oid, Error> {
-        let oid = self
-            .repo
-            .odb()
-            .map_err(|_| GitTerminalError::CommitObjectError {})?
-            .write(Commit, &blob)
-            .map_err(|_| GitTerminalError::CommitObjectError {})?;
+    fn commit_blob(&self, blob: Vec<u8>) -> Result<String, Error> {
+        let oid = self.backend.write_commit_object(blob)?;
+        self.generated_oids.borrow_mut().push(oid.clone());
 
         Ok(oid)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_into_sessions_splits_on_gap() {
+        let timestamps = vec![0, 100, 200, 10_000, 10_100];
+        let sessions = Committer::group_into_sessions(&timestamps, 1_000);
+
+        assert_eq!(sessions, vec![vec![0, 100, 200], vec![10_000, 10_100]]);
+    }
+
+    #[test]
+    fn group_into_sessions_single_commit_is_its_own_session() {
+        let timestamps = vec![42];
+        let sessions = Committer::group_into_sessions(&timestamps, 1_000);
+
+        assert_eq!(sessions, vec![vec![42]]);
+    }
 
-    fn get_repository(repo: &PathBuf) -> Result<Repository, Error> {
-        let repository = Repository::open(&repo).map_err(|_| RepositoryError::OpenError {})?;
+    #[test]
+    fn build_distribution_empty_history_falls_back_to_none() {
+        let result = Committer::build_distribution(Vec::new(), Duration::hours(2)).unwrap();
 
-        Ok(repository)
+        assert!(result.is_none());
     }
 
-    fn get_author(repo: &Repository) -> Result<String, Error> {
-        let signature = repo
-            .signature()
-            .map_err(|_| RepositoryError::SignatureRetrievalError {})?;
+    #[test]
+    fn extract_email_reads_angle_bracket_portion() {
+        assert_eq!(Committer::extract_email("Jane Doe <jane@example.com>"), "jane@example.com");
+    }
 
-        let name = signature
-            .name()
-            .ok_or(RepositoryError::NameRetrievalError {})?;
+    #[test]
+    fn extract_email_falls_back_on_malformed_signature() {
+        assert_eq!(Committer::extract_email("oops > < backwards"), "oops > < backwards");
+        assert_eq!(Committer::extract_email("no brackets here"), "no brackets here");
+    }
 
-        let email = signature
-            .email()
-            .ok_or(RepositoryError::EmailRetrievalError {})?;
+    #[test]
+    fn compute_hours_estimate_within_session_gap_counts_actual_gap() {
+        let gap = Duration::hours(1).num_seconds();
+        let estimate = Committer::compute_hours_estimate(vec![0, gap]);
 
-        Ok(format!("{} <{}>", name, email))
+        // One session: first-commit addition (2h) + the 1h actual gap.
+        assert_eq!(estimate.total_hours, 3.0);
     }
 
-    fn create_tree(repository: &mut Repository) -> Result<String, Error> {
-        let mut index = repository
-            .index()
-            .map_err(|_| RepositoryError::FindIndexError {})?;
+    #[test]
+    fn compute_hours_estimate_across_session_gap_credits_first_commit_addition() {
+        let gap = Duration::hours(3).num_seconds();
+        let estimate = Committer::compute_hours_estimate(vec![0, gap]);
+
+        // Two sessions: each credited the 2h first-commit addition.
+        assert_eq!(estimate.total_hours, 4.0);
+    }
 
-        let tree = index
-            .write_tree()
-            .map_err(|_| RepositoryError::TreeWriteError {})?;
+    #[test]
+    fn compute_hours_estimate_empty_is_zero() {
+        let estimate = Committer::compute_hours_estimate(Vec::new());
 
-        return Ok(format!("{}", tree));
+        assert_eq!(estimate.total_hours, 0.0);
+        assert_eq!(estimate.avg_hours_per_day, 0.0);
+        assert_eq!(estimate.commits_per_hour, 0.0);
     }
 }
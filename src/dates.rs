@@ -0,0 +1,9 @@
+use chrono::{Date, Datelike, Local, Weekday};
+
+/// Returns true if `date` falls on a weekend and should not receive a commit.
+pub fn should_skip_date(date: Date<Local>) -> bool {
+    match date.weekday() {
+        Weekday::Sat | Weekday::Sun => true,
+        _ => false,
+    }
+}
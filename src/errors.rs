@@ -0,0 +1,40 @@
+use failure::Fail;
+
+/// Errors that can occur while reading or preparing the target repository.
+#[derive(Debug, Fail)]
+pub enum RepositoryError {
+    #[fail(display = "could not open repository")]
+    OpenError {},
+    #[fail(display = "could not retrieve signature")]
+    SignatureRetrievalError {},
+    #[fail(display = "could not retrieve signature name")]
+    NameRetrievalError {},
+    #[fail(display = "could not retrieve signature email")]
+    EmailRetrievalError {},
+    #[fail(display = "could not find repository index")]
+    FindIndexError {},
+    #[fail(display = "could not write tree")]
+    TreeWriteError {},
+}
+
+/// Errors that can occur while writing commits into the repository's object database.
+#[derive(Debug, Fail)]
+pub enum GitTerminalError {
+    #[fail(display = "could not reset head")]
+    ResetHeadError {},
+    #[fail(display = "could not write commit object")]
+    CommitObjectError {},
+}
+
+/// Errors that can occur while persisting or reading back an undo manifest.
+#[derive(Debug, Fail)]
+pub enum ManifestError {
+    #[fail(display = "could not serialize manifest")]
+    SerializeError {},
+    #[fail(display = "could not write manifest to disk")]
+    WriteError {},
+    #[fail(display = "could not read manifest from disk")]
+    ReadError {},
+    #[fail(display = "manifest was written by an incompatible version")]
+    VersionMismatchError {},
+}
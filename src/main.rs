@@ -0,0 +1,30 @@
+mod backend;
+mod committer;
+mod dates;
+mod errors;
+mod manifest;
+mod options;
+mod writer;
+
+use committer::Committer;
+use options::Options;
+use structopt::StructOpt;
+
+fn main() -> Result<(), failure::Error> {
+    let options = Options::from_args();
+    let undo_manifest = options.undo.clone();
+    let dry_run = options.dry_run;
+
+    let committer = Committer::new(options)?;
+
+    match undo_manifest {
+        Some(manifest_path) => committer.undo(&manifest_path)?,
+        None if dry_run => committer.dry_run()?,
+        None => {
+            committer.commit_all()?;
+            committer.estimate_hours();
+        }
+    }
+
+    Ok(())
+}
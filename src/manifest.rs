@@ -0,0 +1,56 @@
+use crate::errors::ManifestError;
+use failure::Error;
+use memmap2::Mmap;
+use rkyv::{Archive, Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Current schema version of the on-disk manifest. Bump on layout changes.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// A record of one run's worth of fabricated commits, persisted as a sidecar file so
+/// the run can be undone later.
+#[derive(Archive, Serialize, Deserialize, Debug)]
+#[archive(check_bytes)]
+pub struct Manifest {
+    pub version: u32,
+    pub original_head: String,
+    pub author: String,
+    pub range_start: i64,
+    pub range_end: i64,
+    pub generated_oids: Vec<String>,
+}
+
+impl Manifest {
+    /// Serializes this manifest to `path` using rkyv's zero-copy format.
+    pub fn write_to(&self, path: &Path) -> Result<(), Error> {
+        let bytes =
+            rkyv::to_bytes::<_, 1024>(self).map_err(|_| ManifestError::SerializeError {})?;
+        let mut file = File::create(path).map_err(|_| ManifestError::WriteError {})?;
+        file.write_all(&bytes)
+            .map_err(|_| ManifestError::WriteError {})?;
+        Ok(())
+    }
+}
+
+/// A manifest file mapped into memory, kept alive alongside the archived view so the
+/// mapping isn't dropped while callers are still reading from it.
+pub struct MappedManifest {
+    mmap: Mmap,
+}
+
+impl MappedManifest {
+    /// Memory-maps the manifest at `path`.
+    pub fn open(path: &Path) -> Result<MappedManifest, Error> {
+        let file = File::open(path).map_err(|_| ManifestError::ReadError {})?;
+        let mmap = unsafe { Mmap::map(&file).map_err(|_| ManifestError::ReadError {})? };
+        Ok(MappedManifest { mmap })
+    }
+
+    /// Validates and returns the archived manifest, without a full deserialize pass.
+    pub fn archived(&self) -> Result<&ArchivedManifest, Error> {
+        rkyv::check_archived_root::<Manifest>(&self.mmap[..])
+            .map_err(|_| ManifestError::ReadError {}.into())
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::NaiveTime;
+use std::path::PathBuf;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Which VCS backend writes the generated commits into the target repository.
+#[derive(Debug, Clone, Copy)]
+pub enum BackendKind {
+    /// The default backend, built on `git2` (libgit2).
+    Git2,
+    /// A pure-Rust backend built on `gix` (gitoxide).
+    Gix,
+}
+
+impl FromStr for BackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BackendKind, String> {
+        match s.to_lowercase().as_str() {
+            "git2" => Ok(BackendKind::Git2),
+            "gix" => Ok(BackendKind::Gix),
+            other => Err(format!("unknown backend '{}', expected 'git2' or 'gix'", other)),
+        }
+    }
+}
+
+/// Command-line options controlling how the fabricated history is generated.
+#[derive(StructOpt, Debug)]
+#[structopt(name = "green-bois-magnet")]
+pub struct Options {
+    /// Path to the repository that will receive the generated commits.
+    #[structopt(long, parse(from_os_str))]
+    pub repo: PathBuf,
+
+    /// How many years back the generated history should span.
+    #[structopt(long, default_value = "1.0")]
+    pub yrs_ago: f64,
+
+    /// Earliest clock time a commit may be made on a given day.
+    #[structopt(long, default_value = "09:00:00")]
+    pub start: NaiveTime,
+
+    /// Latest clock time a commit may be made on a given day.
+    #[structopt(long, default_value = "18:00:00")]
+    pub end: NaiveTime,
+
+    /// Commit message used for every generated commit.
+    #[structopt(long, default_value = "auto-generated commit")]
+    pub msg: String,
+
+    /// Path to a reference repository to learn a realistic commit distribution from.
+    #[structopt(long, parse(from_os_str))]
+    pub reference_repo: Option<PathBuf>,
+
+    /// Gap, in minutes, below which two consecutive commits by the same author are
+    /// considered part of the same coding session when learning from `reference_repo`.
+    #[structopt(long, default_value = "120")]
+    pub max_commit_diff: i64,
+
+    /// Probability, per day, that commits for that day land on a short-lived topic
+    /// branch that is merged back with a real two-parent merge commit.
+    #[structopt(long, default_value = "0.15")]
+    pub merge_probability: f64,
+
+    /// Number of commits written to a topic branch before it is merged back.
+    #[structopt(long, default_value = "3")]
+    pub branch_length: i32,
+
+    /// When set, writes an undo manifest for this run's generated commits to this path.
+    #[structopt(long, parse(from_os_str))]
+    pub manifest_path: Option<PathBuf>,
+
+    /// When set, instead of generating commits, reverses a previous run recorded in the
+    /// undo manifest at this path.
+    #[structopt(long, parse(from_os_str))]
+    pub undo: Option<PathBuf>,
+
+    /// When set, prints the planned commit schedule instead of writing any commits.
+    #[structopt(long)]
+    pub dry_run: bool,
+
+    /// Which backend writes the generated commits: `git2` (default) or `gix`.
+    #[structopt(long, default_value = "git2")]
+    pub backend: BackendKind,
+}
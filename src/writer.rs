@@ -0,0 +1,67 @@
+use chrono::DateTime;
+use chrono::Local;
+use failure::Error;
+
+/// Builds the raw git commit object for the very first commit of the fabricated history.
+pub fn generate_initial_blob(
+    tree: &str,
+    author: &str,
+    message: &str,
+    time: DateTime<Local>,
+) -> Result<String, Error> {
+    Ok(format!(
+        "tree {}\nauthor {} {}\ncommitter {} {}\n\n{}\n",
+        tree,
+        author,
+        time.format("%s %z"),
+        author,
+        time.format("%s %z"),
+        message
+    ))
+}
+
+/// Builds the raw git commit object for a subsequent, single-parent commit.
+pub fn generate_non_initial_blob(
+    tree: &str,
+    parent: &str,
+    author: &str,
+    message: &str,
+    time: DateTime<Local>,
+) -> Result<String, Error> {
+    Ok(format!(
+        "tree {}\nparent {}\nauthor {} {}\ncommitter {} {}\n\n{}\n",
+        tree,
+        parent,
+        author,
+        time.format("%s %z"),
+        author,
+        time.format("%s %z"),
+        message
+    ))
+}
+
+/// Builds the raw git commit object for a merge commit with one `parent` line per
+/// entry in `parents`, in order.
+pub fn generate_merge_blob(
+    tree: &str,
+    parents: &[String],
+    author: &str,
+    message: &str,
+    time: DateTime<Local>,
+) -> Result<String, Error> {
+    let parent_lines: String = parents
+        .iter()
+        .map(|parent| format!("parent {}\n", parent))
+        .collect();
+
+    Ok(format!(
+        "tree {}\n{}author {} {}\ncommitter {} {}\n\n{}\n",
+        tree,
+        parent_lines,
+        author,
+        time.format("%s %z"),
+        author,
+        time.format("%s %z"),
+        message
+    ))
+}